@@ -2,9 +2,14 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, GlobalShortcutManager, Manager, State};
+use std::collections::HashMap;
 use std::sync::Mutex;
 use base64::{Engine as _, engine::general_purpose};
+use enigo::{Enigo, Key, KeyboardControllable, MouseControllable};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ScreenshotResult {
@@ -20,16 +25,119 @@ struct SystemInfo {
     screen_height: u32,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordingResult {
+    output_path: String,
+    frame_count: u32,
+}
+
+// Хэндл активной записи: флаг остановки плюс джойн-хэндл фоновой задачи
+struct RecordingHandle {
+    stop_flag: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<Result<RecordingResult, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FramePayload {
+    sequence: u64,
+    timestamp_ms: u64,
+    data: String,
+}
+
+// Хэндл активного стрима: флаг остановки плюс джойн-хэндл фоновой задачи.
+// `app.emit_all` already broadcasts each frame to every subscribed window, so there is
+// no separate fan-out channel to hold here.
+struct StreamHandle {
+    stop_flag: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<()>,
+}
+
 // Состояние приложения
 struct AppState {
     initialized: Mutex<bool>,
+    recording: Mutex<Option<RecordingHandle>>,
+    streaming: Mutex<Option<StreamHandle>>,
+    // accelerator -> action ("screenshot" | "toggle_recording")
+    shortcuts: Mutex<HashMap<String, String>>,
+    // Whether request_screen_recording_permission / request_accessibility_permission has
+    // been called this run, used to distinguish "denied" from "never asked" (NotDetermined).
+    screen_recording_requested: Mutex<bool>,
+    accessibility_requested: Mutex<bool>,
+}
+
+// CmdOrCtrl+Shift+3/4/5 are reserved by macOS for its own screenshot tools, so a distinct
+// combination is used here to avoid colliding with the common case on that platform.
+const DEFAULT_SHORTCUT_ACCELERATOR: &str = "CmdOrCtrl+Shift+F11";
+const DEFAULT_SHORTCUT_ACTION: &str = "screenshot";
+const DEFAULT_RECORDING_OUTPUT_DIR: &str = "recording";
+const DEFAULT_RECORDING_FPS: u32 = 30;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DisplayInfo {
+    id: u32,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    scale_factor: f32,
+    is_primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CropRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+#[tauri::command]
+async fn desktop_env_list_displays() -> Result<Vec<DisplayInfo>, String> {
+    use screenshots::Screen;
+
+    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
+    Ok(screens
+        .into_iter()
+        .map(|screen| {
+            let info = screen.display_info;
+            DisplayInfo {
+                id: info.id,
+                x: info.x,
+                y: info.y,
+                width: info.width,
+                height: info.height,
+                scale_factor: info.scale_factor,
+                is_primary: info.is_primary,
+            }
+        })
+        .collect())
+}
+
+// Находим экран по id, либо берём первый (основной) по умолчанию
+fn find_screen(display_id: Option<u32>) -> Result<screenshots::Screen, String> {
+    use screenshots::Screen;
+
+    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
+    match display_id {
+        Some(id) => screens
+            .into_iter()
+            .find(|s| s.display_info.id == id)
+            .ok_or_else(|| format!("No screen found with id {}", id)),
+        None => {
+            let primary = screens.iter().position(|s| s.display_info.is_primary).unwrap_or(0);
+            screens.into_iter().nth(primary).ok_or_else(|| "No screen found".to_string())
+        }
+    }
 }
 
 #[tauri::command]
-async fn desktop_env_screenshot() -> Result<ScreenshotResult, String> {
+async fn desktop_env_screenshot(
+    display_id: Option<u32>,
+    region: Option<CropRect>,
+) -> Result<ScreenshotResult, String> {
     println!("Taking screenshot...");
-    
-    match take_screenshot_internal().await {
+
+    match take_screenshot_internal(display_id, region).await {
         Ok(screenshot_data) => Ok(ScreenshotResult {
             success: true,
             screenshot_data: Some(screenshot_data),
@@ -43,30 +151,45 @@ async fn desktop_env_screenshot() -> Result<ScreenshotResult, String> {
     }
 }
 
-async fn take_screenshot_internal() -> Result<String, String> {
-    use screenshots::Screen;
-    
-    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
-    let screen = screens.into_iter().next().ok_or("No screen found")?;
-    
+async fn take_screenshot_internal(
+    display_id: Option<u32>,
+    region: Option<CropRect>,
+) -> Result<String, String> {
+    let screen = find_screen(display_id)?;
+
     let image = screen.capture().map_err(|e| format!("Failed to capture screen: {}", e))?;
-    
+
     // Используем встроенный метод для сохранения в PNG
     let mut png_bytes = Vec::new();
     {
-        use image::{ImageBuffer, Rgba, ImageFormat};
+        use image::{imageops, ImageBuffer, Rgba, ImageFormat};
         use std::io::Cursor;
-        
+
         let (width, height) = (image.width(), image.height());
         let raw_data = image.as_raw().clone(); // Clone the data to own it
-        let img_buffer = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(width, height, raw_data)
+        let mut img_buffer = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(width, height, raw_data)
             .ok_or("Failed to create image buffer")?;
-        
+
+        let img_buffer = match region {
+            Some(rect) => {
+                if rect.width == 0 || rect.height == 0 {
+                    return Err("Crop region must not be zero-sized".to_string());
+                }
+                let right = rect.x.checked_add(rect.width).ok_or("Crop region is out of bounds")?;
+                let bottom = rect.y.checked_add(rect.height).ok_or("Crop region is out of bounds")?;
+                if right > width || bottom > height {
+                    return Err("Crop region is out of bounds".to_string());
+                }
+                imageops::crop(&mut img_buffer, rect.x, rect.y, rect.width, rect.height).to_image()
+            }
+            None => img_buffer,
+        };
+
         let mut cursor = Cursor::new(&mut png_bytes);
         img_buffer.write_to(&mut cursor, ImageFormat::Png)
             .map_err(|e| format!("Failed to encode PNG: {}", e))?;
     }
-    
+
     // Кодируем в base64
     let base64_data = general_purpose::STANDARD.encode(&png_bytes);
     Ok(base64_data)
@@ -74,11 +197,8 @@ async fn take_screenshot_internal() -> Result<String, String> {
 
 #[tauri::command]
 async fn desktop_env_system_info() -> Result<SystemInfo, String> {
-    use screenshots::Screen;
-    
-    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
-    let screen = screens.into_iter().next().ok_or("No screen found")?;
-    
+    let screen = find_screen(None)?;
+
     Ok(SystemInfo {
         platform: std::env::consts::OS.to_string(),
         screen_width: screen.display_info.width,
@@ -98,54 +218,510 @@ async fn desktop_env_init(state: State<'_, AppState>) -> Result<String, String>
     Ok("Desktop environment initialized".to_string())
 }
 
+// Захватываем кадр текущего основного экрана как RGBA-буфер
+fn capture_primary_frame() -> Result<image::ImageBuffer<image::Rgba<u8>, Vec<u8>>, String> {
+    let screen = find_screen(None)?;
+    let image = screen.capture().map_err(|e| format!("Failed to capture screen: {}", e))?;
+    let (width, height) = (image.width(), image.height());
+    let raw_data = image.as_raw().clone();
+    image::ImageBuffer::<image::Rgba<u8>, Vec<u8>>::from_raw(width, height, raw_data)
+        .ok_or_else(|| "Failed to create image buffer".to_string())
+}
+
+// Spawns the capture loop shared by desktop_env_start_recording and the shortcut-triggered
+// toggle_recording action. Capture + PNG encode + disk IO are all blocking, so each frame
+// runs via spawn_blocking; a single dropped/failed frame is skipped rather than aborting
+// the whole recording.
+fn spawn_recording_task(output_path: String, fps: u32) -> RecordingHandle {
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let task_stop_flag = stop_flag.clone();
+    let task_output_path = output_path;
+    let frame_interval = Duration::from_secs_f64(1.0 / fps as f64);
+
+    let task = tokio::spawn(async move {
+        let mut frame_count: u32 = 0;
+        while !task_stop_flag.load(Ordering::Relaxed) {
+            let frame_start = Instant::now();
+
+            let frame_path = format!("{}/frame_{:05}.png", task_output_path, frame_count);
+            let save_result = tokio::task::spawn_blocking(move || {
+                let frame = capture_primary_frame()?;
+                frame
+                    .save(&frame_path)
+                    .map_err(|e| format!("Failed to save frame: {}", e))
+            })
+            .await
+            .map_err(|e| format!("Capture task panicked: {}", e))?;
+
+            if save_result.is_ok() {
+                frame_count += 1;
+            } else if let Err(e) = save_result {
+                eprintln!("Skipping frame {}: {}", frame_count, e);
+            }
+
+            let elapsed = frame_start.elapsed();
+            if elapsed < frame_interval {
+                tokio::time::sleep(frame_interval - elapsed).await;
+            }
+        }
+
+        Ok(RecordingResult {
+            output_path: task_output_path,
+            frame_count,
+        })
+    });
+
+    RecordingHandle { stop_flag, task }
+}
+
 #[tauri::command]
-async fn request_screen_recording_permission() -> Result<bool, String> {
-    #[cfg(target_os = "macos")]
+async fn desktop_env_start_recording(
+    fps: u32,
+    output_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    if fps == 0 {
+        return Err("fps must be greater than 0".to_string());
+    }
+
+    let mut recording = state.recording.lock().unwrap();
+    if recording.is_some() {
+        return Err("Recording is already in progress".to_string());
+    }
+
+    std::fs::create_dir_all(&output_path)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    *recording = Some(spawn_recording_task(output_path, fps));
+    Ok("Recording started".to_string())
+}
+
+#[tauri::command]
+async fn desktop_env_stop_recording(state: State<'_, AppState>) -> Result<RecordingResult, String> {
+    let handle = {
+        let mut recording = state.recording.lock().unwrap();
+        recording.take().ok_or("No recording in progress")?
+    };
+
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    handle
+        .task
+        .await
+        .map_err(|e| format!("Recording task panicked: {}", e))?
+}
+
+#[tauri::command]
+async fn desktop_env_start_stream(
+    fps: u32,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    if fps == 0 {
+        return Err("fps must be greater than 0".to_string());
+    }
+
+    let mut streaming = state.streaming.lock().unwrap();
+    if streaming.is_some() {
+        return Err("Stream is already running".to_string());
+    }
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let task_stop_flag = stop_flag.clone();
+    let frame_interval = Duration::from_secs_f64(1.0 / fps as f64);
+
+    let task = tokio::spawn(async move {
+        let mut sequence: u64 = 0;
+        while !task_stop_flag.load(Ordering::Relaxed) {
+            let frame_start = Instant::now();
+
+            // Capture + PNG encode are blocking, so keep them off the async worker.
+            let encoded = tokio::task::spawn_blocking(|| capture_primary_frame().and_then(encode_frame_png))
+                .await
+                .map_err(|e| format!("Capture task panicked: {}", e))
+                .and_then(|r| r);
+
+            if let Ok(data) = encoded {
+                sequence += 1;
+                let payload = FramePayload {
+                    sequence,
+                    timestamp_ms: std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_millis() as u64)
+                        .unwrap_or(0),
+                    data,
+                };
+                let _ = app.emit_all("desktop_env://frame", payload);
+            }
+
+            let elapsed = frame_start.elapsed();
+            if elapsed < frame_interval {
+                tokio::time::sleep(frame_interval - elapsed).await;
+            }
+        }
+    });
+
+    *streaming = Some(StreamHandle { stop_flag, task });
+    Ok("Stream started".to_string())
+}
+
+#[tauri::command]
+async fn desktop_env_stop_stream(state: State<'_, AppState>) -> Result<(), String> {
+    let handle = {
+        let mut streaming = state.streaming.lock().unwrap();
+        streaming.take().ok_or("No stream running")?
+    };
+
+    handle.stop_flag.store(true, Ordering::Relaxed);
+    handle
+        .task
+        .await
+        .map_err(|e| format!("Stream task panicked: {}", e))?;
+    Ok(())
+}
+
+fn encode_frame_png(frame: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>) -> Result<String, String> {
+    use image::ImageFormat;
+    use std::io::Cursor;
+
+    let mut png_bytes = Vec::new();
+    let mut cursor = Cursor::new(&mut png_bytes);
+    frame
+        .write_to(&mut cursor, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(general_purpose::STANDARD.encode(&png_bytes))
+}
+
+// Выполняет действие, привязанное к глобальному шорткату, и сообщает результат фронтенду
+fn run_shortcut_action(app: &AppHandle, state: &State<'_, AppState>, action: &str) {
+    match action {
+        "screenshot" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = take_screenshot_internal(None, None).await;
+                let _ = app.emit_all(
+                    "desktop_env://shortcut",
+                    serde_json::json!({ "action": "screenshot", "result": result }),
+                );
+            });
+        }
+        "toggle_recording" => {
+            let mut recording = state.recording.lock().unwrap();
+            if let Some(handle) = recording.take() {
+                handle.stop_flag.store(true, Ordering::Relaxed);
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let result = handle.task.await.map_err(|e| format!("Recording task panicked: {}", e));
+                    let _ = app.emit_all(
+                        "desktop_env://shortcut",
+                        serde_json::json!({ "action": "stop_recording", "result": result }),
+                    );
+                });
+            } else {
+                let output_path = DEFAULT_RECORDING_OUTPUT_DIR.to_string();
+                if std::fs::create_dir_all(&output_path).is_ok() {
+                    *recording = Some(spawn_recording_task(output_path, DEFAULT_RECORDING_FPS));
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let _ = app.emit_all(
+                            "desktop_env://shortcut",
+                            serde_json::json!({ "action": "start_recording" }),
+                        );
+                    });
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[tauri::command]
+async fn desktop_env_register_shortcut(
+    accelerator: String,
+    action: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if !matches!(action.as_str(), "screenshot" | "toggle_recording") {
+        return Err(format!("Unknown shortcut action: {}", action));
+    }
+
+    {
+        let shortcuts = state.shortcuts.lock().unwrap();
+        if shortcuts.contains_key(&accelerator) {
+            return Err(format!("Accelerator '{}' is already registered", accelerator));
+        }
+    }
+
+    let mut manager = app.global_shortcut_manager();
+    if manager
+        .is_registered(&accelerator)
+        .map_err(|e| format!("Invalid accelerator '{}': {}", accelerator, e))?
     {
-        use std::process::Command;
-        
-        // Проверяем текущий статус разрешения
-        let output = Command::new("system_profiler")
-            .args(&["SPConfigurationProfileDataType"])
-            .output()
-            .map_err(|e| format!("Failed to check permission: {}", e))?;
-            
-        if !output.status.success() {
-            return Err("Failed to check screen recording permission".to_string());
+        return Err(format!("Accelerator '{}' conflicts with an existing shortcut", accelerator));
+    }
+
+    let handler_app = app.clone();
+    let handler_action = action.clone();
+    manager
+        .register(&accelerator, move || {
+            let state = handler_app.state::<AppState>();
+            run_shortcut_action(&handler_app, &state, &handler_action);
+        })
+        .map_err(|e| format!("Failed to register accelerator '{}': {}", accelerator, e))?;
+
+    state.shortcuts.lock().unwrap().insert(accelerator, action);
+    Ok(())
+}
+
+#[tauri::command]
+async fn desktop_env_unregister_shortcut(
+    accelerator: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut shortcuts = state.shortcuts.lock().unwrap();
+    if shortcuts.remove(&accelerator).is_none() {
+        return Err(format!("Accelerator '{}' is not registered", accelerator));
+    }
+
+    app.global_shortcut_manager()
+        .unregister(&accelerator)
+        .map_err(|e| format!("Failed to unregister accelerator '{}': {}", accelerator, e))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl From<MouseButton> for enigo::MouseButton {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => enigo::MouseButton::Left,
+            MouseButton::Right => enigo::MouseButton::Right,
+            MouseButton::Middle => enigo::MouseButton::Middle,
         }
-        
-        // На macOS нужно запросить разрешение через системный диалог
-        // Это происходит автоматически при первой попытке скриншота
-        return Ok(true);
     }
-    
+}
+
+// `enigo` expects logical points, while `desktop_env_system_info` / `desktop_env_list_displays`
+// report physical pixels. Converts a physical-pixel point (the space callers get from a
+// screenshot) into the logical point `enigo` needs, using the `scale_factor` of whichever
+// display actually contains it.
+fn physical_to_logical_point(x: i32, y: i32) -> Result<(i32, i32), String> {
+    use screenshots::Screen;
+
+    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {}", e))?;
+    let display = screens
+        .iter()
+        .find(|s| {
+            let info = &s.display_info;
+            x >= info.x
+                && x < info.x + info.width as i32
+                && y >= info.y
+                && y < info.y + info.height as i32
+        })
+        .or_else(|| screens.iter().find(|s| s.display_info.is_primary))
+        .or_else(|| screens.first())
+        .ok_or("No screen found")?;
+
+    let scale = display.display_info.scale_factor as f64;
+    Ok(((x as f64 / scale).round() as i32, (y as f64 / scale).round() as i32))
+}
+
+#[tauri::command]
+async fn desktop_env_mouse_move(x: i32, y: i32) -> Result<(), String> {
+    let (x, y) = physical_to_logical_point(x, y)?;
+    let mut enigo = Enigo::new();
+    enigo.mouse_move_to(x, y);
+    Ok(())
+}
+
+#[tauri::command]
+async fn desktop_env_mouse_click(button: MouseButton, double: bool) -> Result<(), String> {
+    let mut enigo = Enigo::new();
+    let button = button.into();
+    enigo.mouse_click(button);
+    if double {
+        enigo.mouse_click(button);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn desktop_env_mouse_scroll(dx: i32, dy: i32) -> Result<(), String> {
+    let mut enigo = Enigo::new();
+    if dx != 0 {
+        enigo.mouse_scroll_x(dx);
+    }
+    if dy != 0 {
+        enigo.mouse_scroll_y(dy);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn desktop_env_key_press(key: String) -> Result<(), String> {
+    let mut enigo = Enigo::new();
+    let key = parse_key(&key)?;
+    enigo.key_click(key);
+    Ok(())
+}
+
+#[tauri::command]
+async fn desktop_env_type_text(text: String) -> Result<(), String> {
+    let mut enigo = Enigo::new();
+    enigo.key_sequence(&text);
+    Ok(())
+}
+
+// Разбираем имя клавиши в вариант enigo::Key. Одиночные символы передаются как Key::Layout.
+fn parse_key(name: &str) -> Result<Key, String> {
+    let key = match name.to_lowercase().as_str() {
+        "enter" | "return" => Key::Return,
+        "tab" => Key::Tab,
+        "space" => Key::Space,
+        "backspace" => Key::Backspace,
+        "delete" => Key::Delete,
+        "escape" | "esc" => Key::Escape,
+        "up" => Key::UpArrow,
+        "down" => Key::DownArrow,
+        "left" => Key::LeftArrow,
+        "right" => Key::RightArrow,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Key::Layout(c),
+                _ => return Err(format!("Unknown key: {}", name)),
+            }
+        }
+    };
+    Ok(key)
+}
+
+// `CGPreflightScreenCaptureAccess` and `AXIsProcessTrustedWithOptions` only ever report
+// trusted/not-trusted, with no public API to tell "denied" apart from "never asked". We
+// approximate `NotDetermined` ourselves: until the matching `request_*_permission` command
+// has been called at least once this run, an untrusted result is reported as not-determined
+// rather than denied.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum PermissionState {
+    Granted,
+    Denied,
+    NotDetermined,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PermissionStatus {
+    screen_recording: PermissionState,
+    accessibility: PermissionState,
+}
+
+// Реальные TCC-проверки доступны только на macOS; на остальных платформах разрешение не требуется.
+#[cfg(target_os = "macos")]
+mod macos_permissions {
+    use core_foundation::base::{CFTypeRef, TCFType};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::string::CFString;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrustedWithOptions(options: CFTypeRef) -> bool;
+    }
+
+    pub fn screen_recording_granted() -> bool {
+        unsafe { CGPreflightScreenCaptureAccess() }
+    }
+
+    pub fn request_screen_recording_access() -> bool {
+        unsafe { CGRequestScreenCaptureAccess() }
+    }
+
+    // `prompt` surfaces the system accessibility dialog when the app isn't trusted yet.
+    pub fn accessibility_trusted(prompt: bool) -> bool {
+        let key = CFString::new("AXTrustedCheckOptionPrompt");
+        let value = CFBoolean::from(prompt);
+        let options = CFDictionary::from_CFType_pairs(&[(key.as_CFType(), value.as_CFType())]);
+        unsafe { AXIsProcessTrustedWithOptions(options.as_concrete_TypeRef() as CFTypeRef) }
+    }
+}
+
+#[tauri::command]
+async fn desktop_env_permission_status(state: State<'_, AppState>) -> Result<PermissionStatus, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let screen_recording_requested = *state.screen_recording_requested.lock().unwrap();
+        let accessibility_requested = *state.accessibility_requested.lock().unwrap();
+
+        Ok(PermissionStatus {
+            screen_recording: if macos_permissions::screen_recording_granted() {
+                PermissionState::Granted
+            } else if screen_recording_requested {
+                PermissionState::Denied
+            } else {
+                PermissionState::NotDetermined
+            },
+            accessibility: if macos_permissions::accessibility_trusted(false) {
+                PermissionState::Granted
+            } else if accessibility_requested {
+                PermissionState::Denied
+            } else {
+                PermissionState::NotDetermined
+            },
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = &state;
+        Ok(PermissionStatus {
+            screen_recording: PermissionState::Granted,
+            accessibility: PermissionState::Granted,
+        })
+    }
+}
+
+#[tauri::command]
+async fn request_screen_recording_permission(state: State<'_, AppState>) -> Result<bool, String> {
+    #[cfg(target_os = "macos")]
+    {
+        *state.screen_recording_requested.lock().unwrap() = true;
+        // Surfaces the system screen-recording dialog if the app isn't trusted yet.
+        Ok(macos_permissions::request_screen_recording_access())
+    }
+
     #[cfg(not(target_os = "macos"))]
     {
+        let _ = &state;
         Ok(true) // На других платформах разрешение не требуется
     }
 }
 
 #[tauri::command]
-async fn request_accessibility_permission() -> Result<bool, String> {
+async fn request_accessibility_permission(state: State<'_, AppState>) -> Result<bool, String> {
     #[cfg(target_os = "macos")]
     {
-        use std::process::Command;
-        
-        // Открываем настройки доступности
-        let output = Command::new("open")
-            .args(&["/System/Library/PreferencePanes/Security.prefPane"])
-            .output()
-            .map_err(|e| format!("Failed to open accessibility settings: {}", e))?;
-            
-        if !output.status.success() {
-            return Err("Failed to open accessibility settings".to_string());
-        }
-        
-        return Ok(true);
+        *state.accessibility_requested.lock().unwrap() = true;
+        // Passing `true` surfaces the system accessibility prompt instead of just reading the state.
+        Ok(macos_permissions::accessibility_trusted(true))
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
+        let _ = &state;
         Ok(true) // На других платформах разрешение не требуется
     }
 }
@@ -155,15 +731,58 @@ pub fn run() {
     tauri::Builder::default()
         .manage(AppState {
             initialized: Mutex::new(false),
+            recording: Mutex::new(None),
+            streaming: Mutex::new(None),
+            shortcuts: Mutex::new(HashMap::new()),
+            screen_recording_requested: Mutex::new(false),
+            accessibility_requested: Mutex::new(false),
         })
         .invoke_handler(tauri::generate_handler![
             desktop_env_screenshot,
+            desktop_env_list_displays,
             desktop_env_system_info,
             desktop_env_status,
             desktop_env_init,
+            desktop_env_mouse_move,
+            desktop_env_mouse_click,
+            desktop_env_mouse_scroll,
+            desktop_env_key_press,
+            desktop_env_type_text,
+            desktop_env_start_recording,
+            desktop_env_stop_recording,
+            desktop_env_start_stream,
+            desktop_env_stop_stream,
+            desktop_env_register_shortcut,
+            desktop_env_unregister_shortcut,
+            desktop_env_permission_status,
             request_screen_recording_permission,
             request_accessibility_permission
         ])
+        .setup(|app| {
+            let handle = app.handle();
+            let state = handle.state::<AppState>();
+            let mut manager = app.global_shortcut_manager();
+            let action_handle = handle.clone();
+            // A taken default accelerator (e.g. another app already owns it) shouldn't block startup.
+            match manager.register(DEFAULT_SHORTCUT_ACCELERATOR, move || {
+                let state = action_handle.state::<AppState>();
+                run_shortcut_action(&action_handle, &state, DEFAULT_SHORTCUT_ACTION);
+            }) {
+                Ok(()) => {
+                    state.shortcuts.lock().unwrap().insert(
+                        DEFAULT_SHORTCUT_ACCELERATOR.to_string(),
+                        DEFAULT_SHORTCUT_ACTION.to_string(),
+                    );
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Failed to register default shortcut '{}': {}",
+                        DEFAULT_SHORTCUT_ACCELERATOR, e
+                    );
+                }
+            }
+            Ok(())
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }